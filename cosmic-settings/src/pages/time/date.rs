@@ -3,7 +3,7 @@
 
 use std::str::FromStr;
 
-use chrono::{Datelike, Timelike};
+use chrono::{Datelike, Duration, NaiveDateTime, Offset, Timelike};
 use cosmic::{
     cosmic_config::{self, ConfigGet, ConfigSet},
     widget::{self, dropdown, settings},
@@ -23,6 +23,86 @@ use tracing::error;
 
 crate::cache_dynamic_lazy! {
     static WEEKDAYS: [String; 4] = [fl!("time-format", "friday"), fl!("time-format", "saturday"), fl!("time-format", "sunday"), fl!("time-format", "monday")];
+    static WEEKDAY_STYLES: [String; 2] = [fl!("time-format", "weekday-short"), fl!("time-format", "weekday-long")];
+    static MONTH_STYLES: [String; 3] = [fl!("time-format", "month-numeric"), fl!("time-format", "month-short"), fl!("time-format", "month-long")];
+}
+
+const DEFAULT_FORMAT: &str = "%A, %B %d    %H:%M";
+
+/// The style used to render the weekday name in the built-in date/time display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeekdayStyle {
+    Short,
+    Long,
+}
+
+/// The style used to render the month in the built-in date/time display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonthStyle {
+    Numeric,
+    Short,
+    Long,
+}
+
+/// The set of fields shown in the built-in (non-custom-format) date/time display.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayComponents {
+    pub military_time: bool,
+    pub show_seconds: bool,
+    pub show_weekday: bool,
+    pub weekday_style: WeekdayStyle,
+    pub show_timezone: bool,
+    pub month_style: MonthStyle,
+}
+
+/// The set of allowed values for one field of a [`CalendarEvent`]; `None` means
+/// "any value is allowed" (a bare `*`).
+#[derive(Debug, Clone)]
+struct FieldSet {
+    values: Option<std::collections::BTreeSet<u32>>,
+}
+
+impl FieldSet {
+    fn any() -> Self {
+        Self { values: None }
+    }
+
+    fn matches(&self, value: u32) -> bool {
+        match &self.values {
+            None => true,
+            Some(values) => values.contains(&value),
+        }
+    }
+
+    /// The smallest allowed value, for resetting a lower field after a carry.
+    fn first(&self) -> u32 {
+        self.values
+            .as_ref()
+            .and_then(|values| values.iter().next().copied())
+            .unwrap_or(0)
+    }
+
+    /// The smallest allowed value that is `>= from`, for snapping a field up
+    /// to the next candidate.
+    fn next_at_or_after(&self, from: u32, max: u32) -> Option<u32> {
+        match &self.values {
+            None => Some(from),
+            Some(values) => values.range(from..=max).next().copied(),
+        }
+    }
+}
+
+/// A parsed systemd `OnCalendar`-style expression:
+/// `[weekdays] [year-month-day] [hour:minute:second]`.
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    weekdays: FieldSet,
+    years: FieldSet,
+    months: FieldSet,
+    days: FieldSet,
+    hours: FieldSet,
+    minutes: FieldSet,
+    seconds: FieldSet,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +110,56 @@ pub struct Info {
     pub ntp_enabled: bool,
     pub timezone_id: Option<usize>,
     pub timezone_list: Vec<String>,
+    pub locale_list: Vec<String>,
+}
+
+/// A single strftime-style conversion specifier supported by the custom format string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    YearLong,
+    YearShort,
+    MonthNumeric,
+    MonthLong,
+    MonthShort,
+    DayZeroPadded,
+    DaySpacePadded,
+    WeekdayLong,
+    WeekdayShort,
+    Hour24,
+    Hour12,
+    Minute,
+    Second,
+    Period,
+    TimezoneName,
+    TimezoneOffset,
+    Percent,
+}
+
+/// A piece of a parsed format template: either text to copy verbatim, or a field
+/// whose value is resolved from the current moment and locale at render time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    Literal(String),
+    Field(Token),
+}
+
+/// A point in time paired with the numeric components it was built from, so that
+/// both icu-backed locale formatting (month/weekday names) and plain strftime-style
+/// numeric fields can be rendered from the same value without recomputing "now".
+#[derive(Debug, Clone)]
+pub struct Moment {
+    pub date: DateTime<Iso>,
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub offset: chrono::FixedOffset,
+    /// This moment's zone abbreviation (e.g. "CET"), when known (world
+    /// clocks, or the system clock once its configured zone has been
+    /// resolved). `None` falls back to the numeric UTC offset for `%Z`.
+    pub zone_name: Option<String>,
 }
 
 pub struct Page {
@@ -38,10 +168,34 @@ pub struct Page {
     military_time: bool,
     ntp_enabled: bool,
     show_date_in_top_panel: bool,
-    local_time: Option<DateTime<Iso>>,
+    local_time: Option<Moment>,
     timezone: Option<usize>,
     timezone_list: Vec<String>,
     formatted_date: String,
+    custom_format: String,
+    custom_format_items: Vec<Item>,
+    format_preview: String,
+    show_seconds: bool,
+    show_weekday: bool,
+    weekday_style: WeekdayStyle,
+    show_timezone: bool,
+    month_style: MonthStyle,
+    locale_list: Vec<String>,
+    locale_override: Option<String>,
+    world_clocks: Vec<String>,
+    world_clock_previews: Vec<(String, String)>,
+    calendar_month_offset: i32,
+    /// The locale `update_local_time()` last resolved successfully, cached so
+    /// the calendar view can reuse it instead of re-resolving (and silently
+    /// swallowing errors) inside a `view` closure.
+    calendar_locale: Locale,
+    resync_schedule: String,
+    resync_preview: String,
+    resync_generation: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Mirrors `ntp_enabled` for the detached resync-schedule task, which
+    /// can't borrow `&self` and would otherwise force NTP on even after the
+    /// user has turned the "Automatic" toggle off.
+    ntp_enabled_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
 
 impl Default for Page {
@@ -70,6 +224,71 @@ impl Default for Page {
                 true
             });
 
+        let custom_format = cosmic_applet_config
+            .get("custom_format")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'custom_format'");
+                DEFAULT_FORMAT.to_string()
+            });
+
+        let custom_format_items =
+            try_parse_format_template(&custom_format).unwrap_or_else(|| {
+                parse_format_template(DEFAULT_FORMAT)
+            });
+
+        let show_seconds = cosmic_applet_config
+            .get("show_seconds")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'show_seconds'");
+                false
+            });
+
+        let show_weekday = cosmic_applet_config
+            .get("show_weekday")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'show_weekday'");
+                false
+            });
+
+        let weekday_style = match cosmic_applet_config.get::<usize>("weekday_style") {
+            Ok(1) => WeekdayStyle::Long,
+            _ => WeekdayStyle::Short,
+        };
+
+        let show_timezone = cosmic_applet_config
+            .get("show_timezone")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'show_timezone'");
+                false
+            });
+
+        let month_style = match cosmic_applet_config.get::<usize>("month_style") {
+            Ok(0) => MonthStyle::Numeric,
+            Ok(1) => MonthStyle::Short,
+            _ => MonthStyle::Long,
+        };
+
+        let locale_override = cosmic_applet_config
+            .get("locale_override")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'locale_override'");
+                None
+            });
+
+        let world_clocks = cosmic_applet_config
+            .get("world_clocks")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'world_clocks'");
+                Vec::new()
+            });
+
+        let resync_schedule = cosmic_applet_config
+            .get("resync_schedule")
+            .unwrap_or_else(|err| {
+                error!(?err, "Failed to read config 'resync_schedule'");
+                String::new()
+            });
+
         Self {
             cosmic_applet_config,
             first_day_of_week,
@@ -80,6 +299,24 @@ impl Default for Page {
             show_date_in_top_panel,
             timezone: None,
             timezone_list: Vec::new(),
+            custom_format,
+            custom_format_items,
+            format_preview: String::new(),
+            show_seconds,
+            show_weekday,
+            weekday_style,
+            show_timezone,
+            month_style,
+            locale_list: Vec::new(),
+            locale_override,
+            world_clocks,
+            world_clock_previews: Vec::new(),
+            calendar_month_offset: 0,
+            calendar_locale: Locale::UND,
+            resync_schedule,
+            resync_preview: String::new(),
+            resync_generation: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            ntp_enabled_flag: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
         }
     }
 }
@@ -91,7 +328,9 @@ impl page::Page<crate::pages::Message> for Page {
     ) -> Option<page::Content> {
         Some(vec![
             sections.insert(date()),
+            sections.insert(calendar()),
             sections.insert(timezone()),
+            sections.insert(world_clocks()),
             sections.insert(format()),
         ])
     }
@@ -107,6 +346,14 @@ impl page::Page<crate::pages::Message> for Page {
         _page: cosmic_settings_page::Entity,
         _sender: tokio::sync::mpsc::Sender<crate::pages::Message>,
     ) -> Command<crate::pages::Message> {
+        match self.restart_resync_schedule() {
+            Ok(Some(next)) => {
+                self.resync_preview = next.format("%Y-%m-%d %H:%M:%S").to_string();
+            }
+            Ok(None) => {}
+            Err(err) => error!(err, "Invalid saved resync schedule"),
+        }
+
         cosmic::command::future(async move {
             let client = match zbus::Connection::system().await {
                 Ok(client) => client,
@@ -128,10 +375,13 @@ impl page::Page<crate::pages::Message> for Page {
 
             let timezone = timedate_proxy.timezone().await.unwrap_or_default();
 
+            let locale_list = available_locales().await;
+
             Message::Refresh(Info {
                 ntp_enabled,
                 timezone_id: timezone_list.iter().position(|tz| tz == &timezone),
                 timezone_list,
+                locale_list,
             })
         })
         .map(crate::pages::Message::DateAndTime)
@@ -143,6 +393,8 @@ impl Page {
         match message {
             Message::Automatic(enable) => {
                 self.ntp_enabled = enable;
+                self.ntp_enabled_flag
+                    .store(enable, std::sync::atomic::Ordering::SeqCst);
 
                 tokio::task::spawn(async move {
                     let client = match zbus::Connection::system().await {
@@ -193,6 +445,76 @@ impl Page {
                 }
             }
 
+            Message::ShowSeconds(enable) => {
+                self.show_seconds = enable;
+                self.update_local_time();
+
+                if let Err(err) = self.cosmic_applet_config.set("show_seconds", enable) {
+                    error!(?err, "Failed to set config 'show_seconds'");
+                }
+            }
+
+            Message::ShowWeekday(enable) => {
+                self.show_weekday = enable;
+                self.update_local_time();
+
+                if let Err(err) = self.cosmic_applet_config.set("show_weekday", enable) {
+                    error!(?err, "Failed to set config 'show_weekday'");
+                }
+            }
+
+            Message::WeekdayStyle(index) => {
+                self.weekday_style = if index == 1 {
+                    WeekdayStyle::Long
+                } else {
+                    WeekdayStyle::Short
+                };
+                self.update_local_time();
+
+                if let Err(err) = self.cosmic_applet_config.set("weekday_style", index) {
+                    error!(?err, "Failed to set config 'weekday_style'");
+                }
+            }
+
+            Message::ShowTimezone(enable) => {
+                self.show_timezone = enable;
+                self.update_local_time();
+
+                if let Err(err) = self.cosmic_applet_config.set("show_timezone", enable) {
+                    error!(?err, "Failed to set config 'show_timezone'");
+                }
+            }
+
+            Message::MonthStyle(index) => {
+                self.month_style = match index {
+                    0 => MonthStyle::Numeric,
+                    1 => MonthStyle::Short,
+                    _ => MonthStyle::Long,
+                };
+                self.update_local_time();
+
+                if let Err(err) = self.cosmic_applet_config.set("month_style", index) {
+                    error!(?err, "Failed to set config 'month_style'");
+                }
+            }
+
+            Message::CustomFormat(template) => {
+                // A template that fails to parse into at least one item (e.g. an
+                // empty field) falls back to whatever was last valid.
+                if let Some(items) = try_parse_format_template(&template) {
+                    self.custom_format = template;
+                    self.custom_format_items = items;
+                    self.update_local_time();
+
+                    if let Err(err) = self
+                        .cosmic_applet_config
+                        .set("custom_format", &self.custom_format)
+                    {
+                        error!(?err, "Failed to set config 'custom_format'");
+                    }
+                }
+            }
+
             Message::Timezone(timezone_id) => {
                 self.timezone = Some(timezone_id);
 
@@ -223,15 +545,93 @@ impl Page {
             }
 
             Message::Error(why) => {
-                tracing::error!(why, "failed to set timezone");
+                tracing::error!(why, "date & time settings error");
             }
 
             Message::UpdateTime => self.update_local_time(),
 
+            Message::LocaleOverride(index) => {
+                self.locale_override = if index == 0 {
+                    None
+                } else {
+                    self.locale_list.get(index - 1).cloned()
+                };
+                self.update_local_time();
+
+                if let Err(err) = self
+                    .cosmic_applet_config
+                    .set("locale_override", &self.locale_override)
+                {
+                    error!(?err, "Failed to set config 'locale_override'");
+                }
+            }
+
+            Message::CalendarMonthDelta(delta) => {
+                // Only moves which month is displayed; the system clock is untouched.
+                self.calendar_month_offset += delta;
+            }
+
+            Message::ResyncSchedule(expr) => {
+                self.resync_schedule = expr.clone();
+
+                if let Err(err) = self
+                    .cosmic_applet_config
+                    .set("resync_schedule", &self.resync_schedule)
+                {
+                    error!(?err, "Failed to set config 'resync_schedule'");
+                }
+
+                match self.restart_resync_schedule() {
+                    Ok(Some(next)) => {
+                        self.resync_preview = next.format("%Y-%m-%d %H:%M:%S").to_string();
+                    }
+                    Ok(None) => self.resync_preview = String::new(),
+                    Err(err) => {
+                        self.resync_preview = String::new();
+                        return self.update(Message::Error(err));
+                    }
+                }
+            }
+
+            Message::AddWorldClock(timezone_id) => {
+                if let Some(zone_id) = self.timezone_list.get(timezone_id) {
+                    if !self.world_clocks.iter().any(|zone| zone == zone_id) {
+                        self.world_clocks.push(zone_id.clone());
+                        self.update_local_time();
+                        self.save_world_clocks();
+                    }
+                }
+            }
+
+            Message::RemoveWorldClock(index) => {
+                if index < self.world_clocks.len() {
+                    self.world_clocks.remove(index);
+                    self.update_local_time();
+                    self.save_world_clocks();
+                }
+            }
+
+            Message::MoveWorldClock(index, move_up) => {
+                let target = if move_up {
+                    index.checked_sub(1)
+                } else {
+                    (index + 1 < self.world_clocks.len()).then_some(index + 1)
+                };
+
+                if let Some(target) = target {
+                    self.world_clocks.swap(index, target);
+                    self.update_local_time();
+                    self.save_world_clocks();
+                }
+            }
+
             Message::Refresh(info) => {
                 self.ntp_enabled = info.ntp_enabled;
+                self.ntp_enabled_flag
+                    .store(info.ntp_enabled, std::sync::atomic::Ordering::SeqCst);
                 self.timezone_list = info.timezone_list;
                 self.timezone = info.timezone_id;
+                self.locale_list = info.locale_list;
 
                 self.update_local_time();
             }
@@ -243,26 +643,104 @@ impl Page {
     }
 
     pub fn update_local_time(&mut self) {
-        self.local_time = Some(update_local_time());
+        let now = chrono::Local::now();
+        let mut moment = moment_from_offset_datetime(now);
+        moment.zone_name = self
+            .timezone
+            .and_then(|idx| self.timezone_list.get(idx))
+            .and_then(|zone_id| zone_id.parse::<chrono_tz::Tz>().ok())
+            .map(|tz| now.with_timezone(&tz).offset().to_string());
+        self.local_time = Some(moment.clone());
 
-        self.formatted_date = match self.local_time {
-            Some(ref time) => format_date(time, self.military_time),
-            None => fl!("unknown"),
+        let components = DisplayComponents {
+            military_time: self.military_time,
+            show_seconds: self.show_seconds,
+            show_weekday: self.show_weekday,
+            weekday_style: self.weekday_style,
+            show_timezone: self.show_timezone,
+            month_style: self.month_style,
+        };
+
+        match locale(self.locale_override.as_deref()) {
+            Ok(active_locale) => {
+                self.formatted_date = format_date(&moment, &components, &active_locale);
+                self.format_preview =
+                    render_format(&self.custom_format_items, &moment, &active_locale);
+                self.calendar_locale = active_locale.clone();
+
+                let now_utc = now.with_timezone(&chrono::Utc);
+                self.world_clock_previews = self
+                    .world_clocks
+                    .iter()
+                    .filter_map(|zone_id| {
+                        let moment = world_clock_moment(now_utc, zone_id)?;
+                        Some((zone_id.clone(), format_date(&moment, &components, &active_locale)))
+                    })
+                    .collect();
+            }
+            Err(err) => {
+                let _ = self.update(Message::Error(err));
+                self.formatted_date = fl!("unknown");
+                self.format_preview = fl!("unknown");
+                self.world_clock_previews.clear();
+            }
         }
     }
+
+    fn save_world_clocks(&self) {
+        if let Err(err) = self.cosmic_applet_config.set("world_clocks", &self.world_clocks) {
+            error!(?err, "Failed to set config 'world_clocks'");
+        }
+    }
+
+    /// Cancels whatever resync loop is currently running and, if
+    /// `resync_schedule` is non-empty, parses it and spawns a fresh one.
+    /// Returns the next scheduled sync time, if any.
+    fn restart_resync_schedule(&mut self) -> Result<Option<NaiveDateTime>, String> {
+        let generation = self
+            .resync_generation
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            + 1;
+
+        if self.resync_schedule.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let event = parse_calendar_event(&self.resync_schedule)?;
+        let now = chrono::Local::now().naive_local();
+        let next = next_trigger(&event, now);
+
+        let generation_guard = self.resync_generation.clone();
+        let ntp_enabled = self.ntp_enabled_flag.clone();
+        tokio::task::spawn(run_resync_schedule(event, generation_guard, generation, ntp_enabled));
+
+        Ok(next)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum Message {
+    AddWorldClock(usize),
     Automatic(bool),
+    CalendarMonthDelta(i32),
+    CustomFormat(String),
     Error(String),
+    LocaleOverride(usize),
     MilitaryTime(bool),
+    MonthStyle(usize),
+    MoveWorldClock(usize, bool),
     None,
     FirstDayOfWeek(usize),
     Refresh(Info),
+    RemoveWorldClock(usize),
+    ResyncSchedule(String),
     ShowDate(bool),
+    ShowSeconds(bool),
+    ShowTimezone(bool),
+    ShowWeekday(bool),
     Timezone(usize),
     UpdateTime,
+    WeekdayStyle(usize),
 }
 
 impl page::AutoBind<crate::pages::Message> for Page {}
@@ -272,12 +750,14 @@ fn date() -> Section<crate::pages::Message> {
 
     let auto = descriptions.insert(fl!("time-date", "auto"));
     let title = descriptions.insert(fl!("time-date"));
+    let resync_schedule = descriptions.insert(fl!("time-date", "resync-schedule"));
+    let resync_preview = descriptions.insert(fl!("time-date", "resync-next"));
 
     Section::default()
         .title(fl!("time-date"))
         .descriptions(descriptions)
         .view::<Page>(move |_binder, page, section| {
-            settings::view_section(&section.title)
+            let mut section_view = settings::view_section(&section.title)
                 .add(
                     settings::item::builder(&*section.descriptions[auto])
                         .toggler(page.ntp_enabled, Message::Automatic),
@@ -286,6 +766,104 @@ fn date() -> Section<crate::pages::Message> {
                     &*section.descriptions[title],
                     widget::text(&page.formatted_date),
                 ))
+                .add(
+                    settings::item::builder(&section.descriptions[resync_schedule]).control(
+                        widget::text_input("Mon..Fri *-*-* 03:00:00", &page.resync_schedule)
+                            .on_input(Message::ResyncSchedule),
+                    ),
+                );
+
+            if !page.resync_preview.is_empty() {
+                section_view = section_view.add(settings::item(
+                    &section.descriptions[resync_preview],
+                    widget::text(&page.resync_preview),
+                ));
+            }
+
+            section_view
+                .apply(cosmic::Element::from)
+                .map(crate::pages::Message::DateAndTime)
+        })
+}
+
+fn calendar() -> Section<crate::pages::Message> {
+    let mut descriptions = Slab::new();
+
+    let nav_desc = descriptions.insert(fl!("time-calendar", "nav"));
+    let header_desc = descriptions.insert(fl!("time-calendar", "weekdays"));
+    let grid_desc = descriptions.insert(fl!("time-calendar", "days"));
+
+    Section::default()
+        .title(fl!("time-calendar"))
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let Some(today) = page.local_time.clone() else {
+                return settings::view_section(&section.title)
+                    .apply(cosmic::Element::from)
+                    .map(crate::pages::Message::DateAndTime);
+            };
+
+            let calendar = build_calendar(
+                &today,
+                page.calendar_month_offset,
+                page.first_day_of_week,
+                &page.calendar_locale,
+            );
+
+            let nav = widget::row::with_children(vec![
+                widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                    .on_press(Message::CalendarMonthDelta(-1))
+                    .into(),
+                widget::text(format!("{}-{:02}", calendar.year, calendar.month)).into(),
+                widget::button::icon(widget::icon::from_name("go-next-symbolic"))
+                    .on_press(Message::CalendarMonthDelta(1))
+                    .into(),
+            ])
+            .spacing(8);
+
+            let header = widget::row::with_children(
+                calendar
+                    .weekday_headers
+                    .iter()
+                    .map(|name| widget::text(name).into())
+                    .collect(),
+            )
+            .spacing(8);
+
+            let mut grid = widget::column::with_capacity(calendar.weeks.len()).spacing(4);
+
+            for week in &calendar.weeks {
+                let cells = week
+                    .iter()
+                    .map(|cell| match cell {
+                        Some(cell) => {
+                            let label = widget::text(format!("{}", cell.day));
+
+                            let label: cosmic::Element<_> = if cell.is_today {
+                                widget::container(label)
+                                    .class(cosmic::theme::Container::Primary)
+                                    .into()
+                            } else if cell.is_weekend {
+                                widget::container(label)
+                                    .class(cosmic::theme::Container::Secondary)
+                                    .into()
+                            } else {
+                                label.into()
+                            };
+
+                            label
+                        }
+                        None => widget::text("").into(),
+                    })
+                    .collect();
+
+                grid = grid.push(widget::row::with_children(cells).spacing(8));
+            }
+
+            settings::view_section(&section.title)
+                .add(settings::item(&*section.descriptions[nav_desc], nav))
+                .add(settings::item(&*section.descriptions[header_desc], header))
+                .add(settings::item(&*section.descriptions[grid_desc], grid))
                 .apply(cosmic::Element::from)
                 .map(crate::pages::Message::DateAndTime)
         })
@@ -297,6 +875,14 @@ fn format() -> Section<crate::pages::Message> {
     let military = descriptions.insert(fl!("time-format", "twenty-four"));
     let first = descriptions.insert(fl!("time-format", "first"));
     let show_date = descriptions.insert(fl!("time-format", "show-date"));
+    let seconds = descriptions.insert(fl!("time-format", "seconds"));
+    let weekday = descriptions.insert(fl!("time-format", "weekday"));
+    let weekday_style = descriptions.insert(fl!("time-format", "weekday-style"));
+    let timezone_abbr = descriptions.insert(fl!("time-format", "timezone-abbr"));
+    let month_style = descriptions.insert(fl!("time-format", "month-style"));
+    let locale_override = descriptions.insert(fl!("time-format", "locale-override"));
+    let custom_format = descriptions.insert(fl!("time-format", "custom-format"));
+    let preview = descriptions.insert(fl!("time-format", "preview"));
 
     Section::default()
         .title(fl!("time-format"))
@@ -333,6 +919,74 @@ fn format() -> Section<crate::pages::Message> {
                     settings::item::builder(&section.descriptions[show_date])
                         .toggler(page.show_date_in_top_panel, Message::ShowDate),
                 )
+                // Seconds toggle
+                .add(
+                    settings::item::builder(&section.descriptions[seconds])
+                        .toggler(page.show_seconds, Message::ShowSeconds),
+                )
+                // Weekday name toggle
+                .add(
+                    settings::item::builder(&section.descriptions[weekday])
+                        .toggler(page.show_weekday, Message::ShowWeekday),
+                )
+                // Weekday name style (short/long)
+                .add(
+                    settings::item::builder(&section.descriptions[weekday_style]).control(
+                        dropdown(
+                            &*WEEKDAY_STYLES,
+                            Some(match page.weekday_style {
+                                WeekdayStyle::Short => 0,
+                                WeekdayStyle::Long => 1,
+                            }),
+                            Message::WeekdayStyle,
+                        ),
+                    ),
+                )
+                // Timezone abbreviation toggle
+                .add(
+                    settings::item::builder(&section.descriptions[timezone_abbr])
+                        .toggler(page.show_timezone, Message::ShowTimezone),
+                )
+                // Month style (numeric/short/long)
+                .add(
+                    settings::item::builder(&section.descriptions[month_style]).control(dropdown(
+                        &*MONTH_STYLES,
+                        Some(match page.month_style {
+                            MonthStyle::Numeric => 0,
+                            MonthStyle::Short => 1,
+                            MonthStyle::Long => 2,
+                        }),
+                        Message::MonthStyle,
+                    )),
+                )
+                // Clock language/region override
+                .add({
+                    let options = std::iter::once(fl!("time-format", "locale-automatic"))
+                        .chain(page.locale_list.iter().cloned())
+                        .collect::<Vec<_>>();
+
+                    let selected = page
+                        .locale_override
+                        .as_ref()
+                        .and_then(|tag| page.locale_list.iter().position(|t| t == tag))
+                        .map_or(0, |index| index + 1);
+
+                    settings::item::builder(&section.descriptions[locale_override]).control(
+                        dropdown(&options, Some(selected), Message::LocaleOverride),
+                    )
+                })
+                // Custom format string
+                .add(
+                    settings::item::builder(&section.descriptions[custom_format]).control(
+                        widget::text_input(DEFAULT_FORMAT, &page.custom_format)
+                            .on_input(Message::CustomFormat),
+                    ),
+                )
+                // Live preview of the custom format string
+                .add(settings::item(
+                    &section.descriptions[preview],
+                    widget::text(&page.format_preview),
+                ))
                 .apply(cosmic::Element::from)
                 .map(crate::pages::Message::DateAndTime)
         })
@@ -359,48 +1013,414 @@ fn timezone() -> Section<crate::pages::Message> {
         })
 }
 
-fn locale() -> Result<Locale, Box<dyn std::error::Error>> {
-    let locale = std::env::var("LANG")?;
-    let locale = locale
+fn world_clocks() -> Section<crate::pages::Message> {
+    let mut descriptions = Slab::new();
+
+    let add = descriptions.insert(fl!("time-world-clocks", "add"));
+
+    Section::default()
+        .title(fl!("time-world-clocks"))
+        .descriptions(descriptions)
+        .view::<Page>(move |_binder, page, section| {
+            let mut view = settings::view_section(&section.title).add(
+                settings::item::builder(&section.descriptions[add]).control(dropdown(
+                    &page.timezone_list,
+                    None,
+                    Message::AddWorldClock,
+                )),
+            );
+
+            for (index, (zone_id, formatted)) in page.world_clock_previews.iter().enumerate() {
+                let last = index + 1 == page.world_clock_previews.len();
+
+                view = view.add(settings::item(
+                    zone_id,
+                    widget::row::with_children(vec![
+                        widget::text(formatted).into(),
+                        widget::button::icon(widget::icon::from_name("go-up-symbolic"))
+                            .on_press_maybe(
+                                (index > 0).then_some(Message::MoveWorldClock(index, true)),
+                            )
+                            .into(),
+                        widget::button::icon(widget::icon::from_name("go-down-symbolic"))
+                            .on_press_maybe(
+                                (!last).then_some(Message::MoveWorldClock(index, false)),
+                            )
+                            .into(),
+                        widget::button::icon(widget::icon::from_name("list-remove-symbolic"))
+                            .on_press(Message::RemoveWorldClock(index))
+                            .into(),
+                    ])
+                    .spacing(8),
+                ));
+            }
+
+            view.apply(cosmic::Element::from)
+                .map(crate::pages::Message::DateAndTime)
+        })
+}
+
+/// A single day cell in the rendered calendar grid.
+#[derive(Debug, Clone, Copy)]
+struct CalendarDay {
+    day: u8,
+    is_weekend: bool,
+    is_today: bool,
+}
+
+/// A month's worth of day cells, laid out row by row with the header already
+/// rotated so `first_day_of_week` is the leftmost column.
+struct CalendarMonth {
+    year: i32,
+    month: u32,
+    weekday_headers: [String; 7],
+    weeks: Vec<[Option<CalendarDay>; 7]>,
+}
+
+/// Builds the name of a weekday from the locale's tables (not an English
+/// constant) by formatting a fixed reference date that happens to fall on it.
+fn weekday_name(weekday: chrono::Weekday, locale: &Locale) -> String {
+    // 2024-01-01 was a Monday, so this always lands on the requested weekday.
+    let reference_date = chrono::NaiveDate::from_ymd_opt(2024, 1, 1 + weekday.num_days_from_monday())
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+
+    render_locale_component(&moment_from_offset_datetime(reference_date), locale, |bag| {
+        bag.weekday = Some(icu::datetime::options::components::Text::Short);
+    })
+}
+
+/// Builds the calendar grid for `today`'s month shifted by `month_offset`,
+/// without touching the system clock.
+fn build_calendar(
+    today: &Moment,
+    month_offset: i32,
+    first_day_of_week: usize,
+    locale: &Locale,
+) -> CalendarMonth {
+    let mut year = today.year;
+    let mut month = i32::from(today.month) + month_offset;
+
+    while month < 1 {
+        month += 12;
+        year -= 1;
+    }
+    while month > 12 {
+        month -= 12;
+        year += 1;
+    }
+
+    let month = month as u32;
+    let first_day_of_week = first_day_of_week % 7;
+
+    let first_of_month = chrono::NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let next_month_first = if month == 12 {
+        chrono::NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        chrono::NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .unwrap();
+    let days_in_month = (next_month_first - first_of_month).num_days() as u8;
+
+    let first_weekday = first_of_month.weekday().num_days_from_monday() as usize;
+    let leading_blanks = (first_weekday + 7 - first_day_of_week) % 7;
+
+    let weekday_headers = std::array::from_fn(|i| {
+        let weekday =
+            chrono::Weekday::try_from(((first_day_of_week + i) % 7) as u8).unwrap();
+        weekday_name(weekday, locale)
+    });
+
+    let mut weeks = Vec::new();
+    let mut week: [Option<CalendarDay>; 7] = [None; 7];
+    let mut col = leading_blanks;
+
+    for day in 1..=days_in_month {
+        let day_weekday = (first_weekday + usize::from(day - 1)) % 7;
+
+        week[col] = Some(CalendarDay {
+            day,
+            is_weekend: day_weekday == 5 || day_weekday == 6,
+            is_today: month_offset == 0 && year == today.year && day == today.day,
+        });
+
+        col += 1;
+        if col == 7 {
+            weeks.push(week);
+            week = [None; 7];
+            col = 0;
+        }
+    }
+
+    if col != 0 {
+        weeks.push(week);
+    }
+
+    CalendarMonth {
+        year,
+        month,
+        weekday_headers,
+        weeks,
+    }
+}
+
+/// Resolves the locale the clock should render with: the user's override if
+/// one is set, otherwise `LANG`. Failures are returned rather than swallowed
+/// so callers can surface them through `Message::Error`.
+fn locale(locale_override: Option<&str>) -> Result<Locale, String> {
+    if let Some(tag) = locale_override {
+        return Locale::from_str(tag)
+            .map_err(|e| format!("Invalid clock locale override {tag:?}: {e:?}"));
+    }
+
+    let lang = std::env::var("LANG").map_err(|e| format!("LANG is not set: {e}"))?;
+    let tag = lang
         .split('.')
         .next()
-        .ok_or(format!("Can't split the locale {locale}"))?;
+        .ok_or_else(|| format!("Can't split the locale {lang}"))?;
+
+    Locale::from_str(tag).map_err(|e| format!("Invalid LANG locale {tag:?}: {e:?}"))
+}
+
+/// Queries `locale -a` for the set of locales ICU can load, for use in the
+/// "Clock language/region" dropdown.
+async fn available_locales() -> Vec<String> {
+    let output = match tokio::process::Command::new("locale").arg("-a").output().await {
+        Ok(output) => output,
+        Err(why) => {
+            error!(?why, "Failed to list available locales");
+            return Vec::new();
+        }
+    };
+
+    let mut locales = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let tag = line.split('.').next()?.replace('_', "-");
+            Locale::from_str(&tag).ok()?;
+            Some(tag)
+        })
+        .collect::<Vec<_>>();
+
+    locales.sort_unstable();
+    locales.dedup();
+    locales
+}
+
+/// Parses a strftime-style template into an ordered list of literal and field
+/// items, scanning left to right. Each `%` consumes the following specifier
+/// character; unknown specifiers are passed through verbatim, and a trailing
+/// lone `%` is treated as a literal `%`.
+fn parse_format_template(template: &str) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+
+        let Some(&specifier) = chars.peek() else {
+            literal.push('%');
+            break;
+        };
+
+        let token = match specifier {
+            'Y' => Token::YearLong,
+            'y' => Token::YearShort,
+            'm' => Token::MonthNumeric,
+            'B' => Token::MonthLong,
+            'b' => Token::MonthShort,
+            'd' => Token::DayZeroPadded,
+            'e' => Token::DaySpacePadded,
+            'A' => Token::WeekdayLong,
+            'a' => Token::WeekdayShort,
+            'H' => Token::Hour24,
+            'I' => Token::Hour12,
+            'M' => Token::Minute,
+            'S' => Token::Second,
+            'p' => Token::Period,
+            'Z' => Token::TimezoneName,
+            'z' => Token::TimezoneOffset,
+            '%' => Token::Percent,
+            _ => {
+                literal.push('%');
+                literal.push(specifier);
+                chars.next();
+                continue;
+            }
+        };
+
+        chars.next();
+
+        if !literal.is_empty() {
+            items.push(Item::Literal(std::mem::take(&mut literal)));
+        }
 
-    let locale = Locale::from_str(locale).map_err(|e| format!("{e:?}"))?;
-    Ok(locale)
+        items.push(Item::Field(token));
+    }
+
+    if !literal.is_empty() {
+        items.push(Item::Literal(literal));
+    }
+
+    items
 }
 
-fn format_date(date: &DateTime<Iso>, military: bool) -> String {
-    let Ok(locale) = locale() else {
+/// Parses `template`, rejecting it outright only when there is nothing to
+/// render; callers should keep using the previous result rather than panic
+/// or show a blank clock.
+fn try_parse_format_template(template: &str) -> Option<Vec<Item>> {
+    if template.is_empty() {
+        return None;
+    }
+
+    Some(parse_format_template(template))
+}
+
+/// Formats a single icu date/time component in isolation, so that locale
+/// tables (month and weekday names) are used instead of English constants.
+fn render_locale_component(
+    moment: &Moment,
+    locale: &Locale,
+    configure: impl FnOnce(&mut icu::datetime::options::components::Bag),
+) -> String {
+    let mut bag = icu::datetime::options::components::Bag::empty();
+    configure(&mut bag);
+
+    let Ok(dtf) = DateTimeFormatter::try_new_experimental(&locale.clone().into(), bag.into())
+    else {
         return String::new();
     };
 
+    dtf.format(&moment.date.to_any())
+        .map(|formatted| formatted.to_string())
+        .unwrap_or_default()
+}
+
+fn render_token(token: Token, moment: &Moment, locale: &Locale) -> String {
+    match token {
+        Token::YearLong => format!("{:04}", moment.year),
+        Token::YearShort => format!("{:02}", moment.year.rem_euclid(100)),
+        Token::MonthNumeric => format!("{:02}", moment.month),
+        Token::MonthLong => render_locale_component(moment, locale, |bag| {
+            bag.month = Some(icu::datetime::options::components::Month::Long);
+        }),
+        Token::MonthShort => render_locale_component(moment, locale, |bag| {
+            bag.month = Some(icu::datetime::options::components::Month::Short);
+        }),
+        Token::DayZeroPadded => format!("{:02}", moment.day),
+        Token::DaySpacePadded => format!("{:2}", moment.day),
+        Token::WeekdayLong => render_locale_component(moment, locale, |bag| {
+            bag.weekday = Some(icu::datetime::options::components::Text::Long);
+        }),
+        Token::WeekdayShort => render_locale_component(moment, locale, |bag| {
+            bag.weekday = Some(icu::datetime::options::components::Text::Short);
+        }),
+        Token::Hour24 => format!("{:02}", moment.hour),
+        Token::Hour12 => {
+            let hour12 = match moment.hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            format!("{hour12:02}")
+        }
+        Token::Minute => format!("{:02}", moment.minute),
+        Token::Second => format!("{:02}", moment.second),
+        Token::Period => {
+            if moment.hour < 12 {
+                fl!("time-format", "am")
+            } else {
+                fl!("time-format", "pm")
+            }
+        }
+        Token::TimezoneName => moment
+            .zone_name
+            .clone()
+            .unwrap_or_else(|| format_utc_offset(moment.offset)),
+        Token::TimezoneOffset => format_utc_offset(moment.offset),
+        Token::Percent => "%".to_string(),
+    }
+}
+
+/// Renders a parsed format template against a specific moment in time.
+fn render_format(items: &[Item], moment: &Moment, locale: &Locale) -> String {
+    let mut out = String::new();
+
+    for item in items {
+        match item {
+            Item::Literal(s) => out.push_str(s),
+            Item::Field(token) => out.push_str(&render_token(*token, moment, locale)),
+        }
+    }
+
+    out
+}
+
+fn format_date(moment: &Moment, components: &DisplayComponents, locale: &Locale) -> String {
     let mut bag = icu::datetime::options::components::Bag::empty();
 
     bag.year = Some(icu::datetime::options::components::Year::Numeric);
     bag.day = Some(icu::datetime::options::components::Day::NumericDayOfMonth);
-    bag.month = Some(icu::datetime::options::components::Month::Long);
+    bag.month = Some(match components.month_style {
+        MonthStyle::Numeric => icu::datetime::options::components::Month::Numeric,
+        MonthStyle::Short => icu::datetime::options::components::Month::Short,
+        MonthStyle::Long => icu::datetime::options::components::Month::Long,
+    });
     bag.hour = Some(icu::datetime::options::components::Numeric::Numeric);
     bag.minute = Some(icu::datetime::options::components::Numeric::Numeric);
+
+    if components.show_seconds {
+        bag.second = Some(icu::datetime::options::components::Numeric::Numeric);
+    }
+
+    if components.show_weekday {
+        bag.weekday = Some(match components.weekday_style {
+            WeekdayStyle::Short => icu::datetime::options::components::Text::Short,
+            WeekdayStyle::Long => icu::datetime::options::components::Text::Long,
+        });
+    }
+
+    // `bag.time_zone_name` needs a zoned input (a `CustomTimeZone`), not the
+    // plain `DateTime<Iso>` we have, so the abbreviation is appended ourselves
+    // below from `moment`'s own zone/offset info rather than asked of the
+    // formatter (which would otherwise panic on the `.expect()` below).
     bag.preferences = Some(icu::datetime::options::preferences::Bag::from_hour_cycle(
-        if military {
+        if components.military_time {
             icu::datetime::options::preferences::HourCycle::H23
         } else {
             icu::datetime::options::preferences::HourCycle::H12
         },
     ));
 
-    let dtf = DateTimeFormatter::try_new_experimental(&locale.into(), bag.into()).unwrap();
+    let dtf = DateTimeFormatter::try_new_experimental(&locale.clone().into(), bag.into()).unwrap();
 
-    dtf.format(&date.to_any())
+    let mut formatted = dtf
+        .format(&moment.date.to_any())
         .expect("can't format value")
-        .to_string()
-}
+        .to_string();
 
-fn update_local_time() -> DateTime<Iso> {
-    let now = chrono::Local::now();
+    if components.show_timezone {
+        let zone = moment
+            .zone_name
+            .clone()
+            .unwrap_or_else(|| format_utc_offset(moment.offset));
+        formatted.push(' ');
+        formatted.push_str(&zone);
+    }
+
+    formatted
+}
 
-    DateTime::try_new_gregorian_datetime(
+fn moment_from_offset_datetime<Tz: chrono::TimeZone>(now: chrono::DateTime<Tz>) -> Moment
+where
+    Tz::Offset: chrono::Offset,
+{
+    let date = DateTime::try_new_gregorian_datetime(
         now.year(),
         now.month() as u8,
         now.day() as u8,
@@ -409,5 +1429,519 @@ fn update_local_time() -> DateTime<Iso> {
         now.second() as u8,
     )
     .unwrap()
-    .to_iso()
+    .to_iso();
+
+    Moment {
+        date,
+        year: now.year(),
+        month: now.month() as u8,
+        day: now.day() as u8,
+        hour: now.hour() as u8,
+        minute: now.minute() as u8,
+        second: now.second() as u8,
+        offset: now.offset().fix(),
+        zone_name: None,
+    }
+}
+
+/// Resolves a world clock's current moment from a single shared UTC sample,
+/// so every clock on the page reflects the same instant instead of drifting
+/// against each other by however long rendering takes.
+fn world_clock_moment(now: chrono::DateTime<chrono::Utc>, zone_id: &str) -> Option<Moment> {
+    let tz: chrono_tz::Tz = zone_id.parse().ok()?;
+    let zoned_now = now.with_timezone(&tz);
+    let mut moment = moment_from_offset_datetime(zoned_now);
+    moment.zone_name = Some(zoned_now.offset().to_string());
+    Some(moment)
+}
+
+/// Formats a UTC offset as `+HHMM`/`-HHMM`, the fallback used when a moment
+/// has no known zone id (e.g. the bare system clock before its zone has been
+/// resolved).
+fn format_utc_offset(offset: chrono::FixedOffset) -> String {
+    let offset_minutes = offset.local_minus_utc() / 60;
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let offset_minutes = offset_minutes.abs();
+    format!("{sign}{:02}{:02}", offset_minutes / 60, offset_minutes % 60)
+}
+
+fn parse_weekday_atom(s: &str) -> Result<u32, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "mon" => Ok(0),
+        "tue" => Ok(1),
+        "wed" => Ok(2),
+        "thu" => Ok(3),
+        "fri" => Ok(4),
+        "sat" => Ok(5),
+        "sun" => Ok(6),
+        other => Err(format!("Unknown weekday {other:?}")),
+    }
+}
+
+fn parse_numeric_atom(s: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| format!("Invalid number {s:?}"))
+}
+
+/// Parses one field of a calendar expression: `*`, a comma list, an
+/// inclusive `a..b` range, or a `base/step` repetition.
+fn parse_field(
+    spec: &str,
+    max: u32,
+    atom: impl Fn(&str) -> Result<u32, String>,
+) -> Result<FieldSet, String> {
+    if spec == "*" {
+        return Ok(FieldSet::any());
+    }
+
+    let mut values = std::collections::BTreeSet::new();
+
+    for part in spec.split(',') {
+        if let Some((base, step)) = part.split_once('/') {
+            let start = if base == "*" { 0 } else { atom(base)? };
+            let step: u32 = step
+                .parse()
+                .map_err(|_| format!("Invalid step {step:?} in {part:?}"))?;
+
+            if step == 0 {
+                return Err(format!("Step cannot be zero in {part:?}"));
+            }
+
+            let mut value = start;
+            while value <= max {
+                values.insert(value);
+                value += step;
+            }
+        } else if let Some((start, end)) = part.split_once("..") {
+            let start = atom(start)?;
+            let end = atom(end)?;
+
+            if start > end {
+                return Err(format!("Invalid range {part:?}: start comes after end"));
+            }
+
+            values.extend(start..=end);
+        } else {
+            values.insert(atom(part)?);
+        }
+    }
+
+    if values.is_empty() {
+        return Err(format!("Field {spec:?} matches nothing"));
+    }
+
+    Ok(FieldSet {
+        values: Some(values),
+    })
+}
+
+/// Parses the subset `[weekdays] [year-month-day] [hour:minute:second]` of
+/// systemd's `OnCalendar` syntax. A missing date defaults to every day; a
+/// missing time defaults to midnight, matching systemd's own convention.
+fn parse_calendar_event(expr: &str) -> Result<CalendarEvent, String> {
+    let expr = expr.trim();
+    if expr.is_empty() {
+        return Err("Resync schedule cannot be empty".to_string());
+    }
+
+    let mut weekdays_spec = None;
+    let mut date_spec = None;
+    let mut time_spec = None;
+
+    for token in expr.split_whitespace() {
+        if token.contains(':') {
+            if time_spec.replace(token).is_some() {
+                return Err(format!("Multiple time fields in {expr:?}"));
+            }
+        } else if token.chars().any(|c| c.is_ascii_alphabetic()) {
+            if weekdays_spec.replace(token).is_some() {
+                return Err(format!("Multiple weekday fields in {expr:?}"));
+            }
+        } else if date_spec.replace(token).is_some() {
+            return Err(format!("Multiple date fields in {expr:?}"));
+        }
+    }
+
+    let weekdays = match weekdays_spec {
+        Some(spec) => parse_field(spec, 6, parse_weekday_atom)?,
+        None => FieldSet::any(),
+    };
+
+    let (years, months, days) = match date_spec {
+        Some(spec) => {
+            let mut fields = spec.splitn(3, '-');
+            let year = fields
+                .next()
+                .ok_or_else(|| format!("Invalid date {spec:?}"))?;
+            let month = fields
+                .next()
+                .ok_or_else(|| format!("Invalid date {spec:?}: missing month"))?;
+            let day = fields
+                .next()
+                .ok_or_else(|| format!("Invalid date {spec:?}: missing day"))?;
+
+            (
+                parse_field(year, 9999, parse_numeric_atom)?,
+                parse_field(month, 12, parse_numeric_atom)?,
+                parse_field(day, 31, parse_numeric_atom)?,
+            )
+        }
+        None => (FieldSet::any(), FieldSet::any(), FieldSet::any()),
+    };
+
+    let (hours, minutes, seconds) = match time_spec {
+        Some(spec) => {
+            let mut fields = spec.splitn(3, ':');
+            let hour = fields
+                .next()
+                .ok_or_else(|| format!("Invalid time {spec:?}"))?;
+            let minute = fields
+                .next()
+                .ok_or_else(|| format!("Invalid time {spec:?}: missing minute"))?;
+            let second = fields.next().unwrap_or("0");
+
+            (
+                parse_field(hour, 23, parse_numeric_atom)?,
+                parse_field(minute, 59, parse_numeric_atom)?,
+                parse_field(second, 59, parse_numeric_atom)?,
+            )
+        }
+        None => {
+            let midnight = FieldSet {
+                values: Some(std::collections::BTreeSet::from([0])),
+            };
+            (midnight.clone(), midnight.clone(), midnight)
+        }
+    };
+
+    Ok(CalendarEvent {
+        weekdays,
+        years,
+        months,
+        days,
+        hours,
+        minutes,
+        seconds,
+    })
+}
+
+fn bump_minute(candidate: NaiveDateTime, event: &CalendarEvent) -> Option<NaiveDateTime> {
+    if candidate.minute() < 59 {
+        candidate
+            .date()
+            .and_hms_opt(candidate.hour(), candidate.minute() + 1, event.seconds.first())
+    } else {
+        bump_hour(candidate, event)
+    }
+}
+
+fn bump_hour(candidate: NaiveDateTime, event: &CalendarEvent) -> Option<NaiveDateTime> {
+    if candidate.hour() < 23 {
+        candidate.date().and_hms_opt(
+            candidate.hour() + 1,
+            event.minutes.first(),
+            event.seconds.first(),
+        )
+    } else {
+        bump_day(candidate, event)
+    }
+}
+
+fn bump_day(candidate: NaiveDateTime, event: &CalendarEvent) -> Option<NaiveDateTime> {
+    let next_date = candidate.date().succ_opt()?;
+    next_date.and_hms_opt(event.hours.first(), event.minutes.first(), event.seconds.first())
+}
+
+/// Finds the next moment at or after `after + 1s` that satisfies `event`,
+/// snapping each field up to its nearest allowed value and carrying into the
+/// next higher field on overflow. Dates that can never exist (e.g. Feb 30)
+/// are skipped over naturally, since candidates are only ever produced by
+/// incrementing a valid calendar date.
+fn next_trigger(event: &CalendarEvent, after: NaiveDateTime) -> Option<NaiveDateTime> {
+    let mut candidate = after + Duration::seconds(1);
+
+    // Bounded so an expression that can truly never match (e.g. Feb 30) terminates.
+    for _ in 0..(20 * 366) {
+        if !event.seconds.matches(candidate.second()) {
+            match event.seconds.next_at_or_after(candidate.second(), 59) {
+                Some(second) => {
+                    candidate = candidate
+                        .date()
+                        .and_hms_opt(candidate.hour(), candidate.minute(), second)?;
+                }
+                None => candidate = bump_minute(candidate, event)?,
+            }
+            continue;
+        }
+
+        if !event.minutes.matches(candidate.minute()) {
+            match event.minutes.next_at_or_after(candidate.minute(), 59) {
+                Some(minute) => {
+                    candidate = candidate.date().and_hms_opt(
+                        candidate.hour(),
+                        minute,
+                        event.seconds.first(),
+                    )?;
+                }
+                None => candidate = bump_hour(candidate, event)?,
+            }
+            continue;
+        }
+
+        if !event.hours.matches(candidate.hour()) {
+            match event.hours.next_at_or_after(candidate.hour(), 23) {
+                Some(hour) => {
+                    candidate = candidate.date().and_hms_opt(
+                        hour,
+                        event.minutes.first(),
+                        event.seconds.first(),
+                    )?;
+                }
+                None => candidate = bump_day(candidate, event)?,
+            }
+            continue;
+        }
+
+        let date = candidate.date();
+        let date_matches = event.years.matches(date.year() as u32)
+            && event.months.matches(date.month())
+            && event.days.matches(date.day())
+            && event.weekdays.matches(date.weekday().num_days_from_monday());
+
+        if !date_matches {
+            candidate = bump_day(candidate, event)?;
+            continue;
+        }
+
+        return Some(candidate);
+    }
+
+    None
+}
+
+/// Performs the same manual resync the NTP toggle triggers. If the user has
+/// "Automatic" turned off, this only logs and leaves `set_ntp` alone, since
+/// this task runs detached from `update()` and must never re-enable NTP
+/// system-wide behind the user's back.
+async fn perform_manual_sync(ntp_enabled: bool) {
+    if !ntp_enabled {
+        tracing::debug!("skipping scheduled resync: automatic time sync is disabled");
+        return;
+    }
+
+    let client = match zbus::Connection::system().await {
+        Ok(client) => client,
+        Err(why) => {
+            tracing::error!(?why, "zbus client error");
+            return;
+        }
+    };
+
+    let timedate_proxy = match TimeDateProxy::new(&client).await {
+        Ok(timedate_proxy) => timedate_proxy,
+        Err(why) => {
+            tracing::error!(?why, "zbus client error");
+            return;
+        }
+    };
+
+    if let Err(why) = timedate_proxy.set_ntp(true, true).await {
+        tracing::error!(?why, "failed to trigger scheduled time resync");
+    }
+}
+
+/// Sleeps until each scheduled trigger and performs a resync, stopping as
+/// soon as a newer call to `restart_resync_schedule` bumps the generation.
+async fn run_resync_schedule(
+    event: CalendarEvent,
+    generation_guard: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    generation: u64,
+    ntp_enabled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    loop {
+        if generation_guard.load(std::sync::atomic::Ordering::SeqCst) != generation {
+            return;
+        }
+
+        let now = chrono::Local::now().naive_local();
+        let Some(trigger) = next_trigger(&event, now) else {
+            tracing::error!("resync schedule never matches a future time");
+            return;
+        };
+
+        let delay = (trigger - now).to_std().unwrap_or(std::time::Duration::ZERO);
+        tokio::time::sleep(delay).await;
+
+        if generation_guard.load(std::sync::atomic::Ordering::SeqCst) != generation {
+            return;
+        }
+
+        perform_manual_sync(ntp_enabled.load(std::sync::atomic::Ordering::SeqCst)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        next_trigger, parse_calendar_event, parse_field, parse_format_template, parse_numeric_atom,
+        render_format, try_parse_format_template, Item, Moment, Token,
+    };
+    use chrono::{Datelike, NaiveDate};
+
+    fn dt(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> chrono::NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, mo, d)
+            .unwrap()
+            .and_hms_opt(h, mi, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn every_day_at_fixed_time() {
+        let event = parse_calendar_event("03:00:00").unwrap();
+        let next = next_trigger(&event, dt(2026, 1, 1, 12, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 1, 2, 3, 0, 0));
+    }
+
+    #[test]
+    fn snaps_forward_within_the_same_day() {
+        let event = parse_calendar_event("03:00:00").unwrap();
+        let next = next_trigger(&event, dt(2026, 1, 1, 1, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 1, 1, 3, 0, 0));
+    }
+
+    #[test]
+    fn weekday_range_skips_to_next_matching_day() {
+        // 2026-01-01 is a Thursday; Mon..Fri should land on Friday.
+        let event = parse_calendar_event("Mon..Fri 03:00:00").unwrap();
+        let next = next_trigger(&event, dt(2026, 1, 1, 12, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 1, 2, 3, 0, 0));
+
+        // From Friday past the trigger time, the next match is Monday.
+        let next = next_trigger(&event, dt(2026, 1, 2, 12, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 1, 5, 3, 0, 0));
+    }
+
+    #[test]
+    fn minute_step_repetition() {
+        let event = parse_calendar_event("*:0/15:00").unwrap();
+        let next = next_trigger(&event, dt(2026, 1, 1, 10, 1, 0)).unwrap();
+        assert_eq!(next, dt(2026, 1, 1, 10, 15, 0));
+
+        let next = next_trigger(&event, dt(2026, 1, 1, 10, 45, 0)).unwrap();
+        assert_eq!(next, dt(2026, 1, 1, 11, 0, 0));
+    }
+
+    #[test]
+    fn impossible_date_rolls_forward_to_a_month_that_has_it() {
+        // February never has a 30th day, so starting from within February
+        // should roll the trigger forward into March.
+        let event = parse_calendar_event("*-*-30 00:00:00").unwrap();
+        let next = next_trigger(&event, dt(2026, 2, 1, 0, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 3, 30, 0, 0, 0));
+    }
+
+    #[test]
+    fn pinned_month_and_impossible_day_never_matches() {
+        // Every February, forever, has no 30th day, so this can never trigger.
+        let event = parse_calendar_event("*-02-30 00:00:00").unwrap();
+        assert_eq!(next_trigger(&event, dt(2026, 1, 1, 0, 0, 0)), None);
+    }
+
+    #[test]
+    fn explicit_date_and_time() {
+        let event = parse_calendar_event("2026-06-15 09:30:00").unwrap();
+        let next = next_trigger(&event, dt(2026, 1, 1, 0, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 6, 15, 9, 30, 0));
+    }
+
+    #[test]
+    fn missing_time_defaults_to_midnight() {
+        let event = parse_calendar_event("2026-06-15").unwrap();
+        let next = next_trigger(&event, dt(2026, 1, 1, 0, 0, 0)).unwrap();
+        assert_eq!(next, dt(2026, 6, 15, 0, 0, 0));
+    }
+
+    #[test]
+    fn rejects_empty_expression() {
+        assert!(parse_calendar_event("   ").is_err());
+    }
+
+    #[test]
+    fn rejects_zero_step() {
+        assert!(parse_calendar_event("*:0/0:00").is_err());
+    }
+
+    #[test]
+    fn rejects_backwards_range() {
+        assert!(parse_field("10..5", 59, parse_numeric_atom).is_err());
+    }
+
+    fn test_moment() -> Moment {
+        Moment {
+            date: icu::calendar::DateTime::try_new_gregorian_datetime(2026, 7, 30, 14, 5, 32)
+                .unwrap()
+                .to_iso(),
+            year: 2026,
+            month: 7,
+            day: 30,
+            hour: 14,
+            minute: 5,
+            second: 32,
+            offset: chrono::FixedOffset::east_opt(0).unwrap(),
+            zone_name: None,
+        }
+    }
+
+    #[test]
+    fn trailing_percent_is_literal() {
+        assert_eq!(
+            parse_format_template("abc%"),
+            vec![Item::Literal("abc%".to_string())]
+        );
+    }
+
+    #[test]
+    fn unknown_specifier_passes_through_verbatim() {
+        assert_eq!(
+            parse_format_template("%q"),
+            vec![Item::Literal("%q".to_string())]
+        );
+    }
+
+    #[test]
+    fn mixed_literal_and_fields() {
+        assert_eq!(
+            parse_format_template("%Y-%m-%d"),
+            vec![
+                Item::Field(Token::YearLong),
+                Item::Literal("-".to_string()),
+                Item::Field(Token::MonthNumeric),
+                Item::Literal("-".to_string()),
+                Item::Field(Token::DayZeroPadded),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_template_is_rejected() {
+        assert!(try_parse_format_template("").is_none());
+    }
+
+    #[test]
+    fn nonempty_template_always_parses() {
+        assert!(try_parse_format_template("%%").is_some());
+    }
+
+    #[test]
+    fn renders_literal_and_numeric_fields() {
+        let items = parse_format_template("%Y-%m-%d %H:%M:%S");
+        let rendered = render_format(&items, &test_moment(), &icu::locid::Locale::UND);
+        assert_eq!(rendered, "2026-07-30 14:05:32");
+    }
+
+    #[test]
+    fn literal_percent_escape_renders_a_single_percent() {
+        let items = parse_format_template("100%%");
+        let rendered = render_format(&items, &test_moment(), &icu::locid::Locale::UND);
+        assert_eq!(rendered, "100%");
+    }
 }